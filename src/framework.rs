@@ -1,65 +1,155 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use crate::Framework;
+use crate::staging_pool::{StagingKind, StagingPool};
+use crate::{Framework, GpuError, GpuResult};
 
-#[cfg(not(target_arch = "wasm32"))]
-impl Default for Framework {
+/// Builder for a [`Framework`], allowing explicit selection of the adapter,
+/// requested device features and limits.
+///
+/// Unlike [`Framework::default`], which hard-codes a high-performance adapter,
+/// requests every adapter feature and panics on failure, the builder surfaces
+/// errors as [`GpuResult`] and lets the caller pick a specific backend, a
+/// software adapter or reduced limits (e.g. for the web).
+pub struct FrameworkBuilder {
+    power_preference: wgpu::PowerPreference,
+    backends: wgpu::Backends,
+    required_features: wgpu::Features,
+    required_limits: Option<wgpu::Limits>,
+    force_fallback_adapter: bool,
+    device_label: Option<String>,
+}
+
+impl Default for FrameworkBuilder {
     fn default() -> Self {
-        let power_preference = wgpu::util::power_preference_from_env()
-            .unwrap_or(wgpu::PowerPreference::HighPerformance);
-        let instance = wgpu::Instance::default();
+        Self {
+            power_preference: wgpu::util::power_preference_from_env()
+                .unwrap_or(wgpu::PowerPreference::HighPerformance),
+            backends: wgpu::Backends::all(),
+            required_features: wgpu::Features::empty(),
+            required_limits: None,
+            force_fallback_adapter: false,
+            device_label: None,
+        }
+    }
+}
+
+impl FrameworkBuilder {
+    /// Creates a [`FrameworkBuilder`] with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-        log::debug!("Requesting device with {:#?}", power_preference);
+    /// Sets the [`wgpu::PowerPreference`] used when requesting the adapter.
+    pub fn power_preference(mut self, power_preference: wgpu::PowerPreference) -> Self {
+        self.power_preference = power_preference;
+        self
+    }
 
-        futures::executor::block_on(async {
-            let adapter = instance
-                .request_adapter(&wgpu::RequestAdapterOptions {
-                    power_preference,
-                    ..Default::default()
-                })
-                .await
-                .expect("Failed at adapter creation.");
+    /// Restricts adapter selection to the given [`wgpu::Backends`].
+    pub fn backends(mut self, backends: wgpu::Backends) -> Self {
+        self.backends = backends;
+        self
+    }
 
-            Self::new(adapter).await
-        })
+    /// Sets the device features to request.
+    ///
+    /// Only the subset also reported by [`wgpu::Adapter::features`] is
+    /// actually requested from the device; see [`FrameworkBuilder::build_async`].
+    pub fn required_features(mut self, required_features: wgpu::Features) -> Self {
+        self.required_features = required_features;
+        self
     }
-}
 
-impl Framework {
-    #[cfg(target_arch = "wasm32")]
-    pub async fn default() -> Self {
-        let power_preference = wgpu::util::power_preference_from_env()
-            .unwrap_or(wgpu::PowerPreference::HighPerformance);
-        let instance = wgpu::Instance::default();
+    /// Sets the device limits that must be satisfied.
+    ///
+    /// When left unset, the adapter's own limits ([`wgpu::Adapter::limits`])
+    /// are requested, matching [`Framework::new`].
+    pub fn required_limits(mut self, required_limits: wgpu::Limits) -> Self {
+        self.required_limits = Some(required_limits);
+        self
+    }
 
-        log::debug!("Requesting device with {:#?}", power_preference);
+    /// Forces the selection of a fallback (typically software) adapter.
+    pub fn force_fallback_adapter(mut self, force_fallback_adapter: bool) -> Self {
+        self.force_fallback_adapter = force_fallback_adapter;
+        self
+    }
+
+    /// Sets the debug label of the created [`wgpu::Device`].
+    pub fn device_label(mut self, device_label: impl Into<String>) -> Self {
+        self.device_label = Some(device_label.into());
+        self
+    }
+
+    /// Requests the adapter and device, returning the built [`Framework`].
+    pub async fn build_async(self) -> GpuResult<Framework> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: self.backends,
+            ..Default::default()
+        });
+
+        log::debug!("Requesting device with {:#?}", self.power_preference);
 
         let adapter = instance
             .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference,
-                ..Default::default()
+                power_preference: self.power_preference,
+                force_fallback_adapter: self.force_fallback_adapter,
+                compatible_surface: None,
             })
             .await
-            .expect("Failed at adapter creation.");
+            .ok_or(GpuError::AdapterNotFound)?;
+
+        // Adapters only ever support a subset of all features, so intersect
+        // rather than pass `self.required_features` through verbatim: asking
+        // for `wgpu::Features::all()` (as `Framework::default` does) would
+        // otherwise fail `request_device` validation on every real adapter.
+        let features = self.required_features & adapter.features();
+        let limits = self.required_limits.unwrap_or_else(|| adapter.limits());
 
-        Self::new(adapter).await
-    }
-    /// Creates a new [`Framework`] instance from a [`wgpu::Adapter`] and a `polling_time`.
-    ///
-    /// Use this method when there are multiple GPUs in use or when a [`wgpu::Surface`] is required.
-    pub async fn new(adapter: wgpu::Adapter) -> Self {
         let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
-                    label: None,
-                    features: adapter.features(), // Change this to allow proper WebGL2 support (in the future™️).
-                    limits: adapter.limits(),     // Bye WebGL2 support :(
+                    label: self.device_label.as_deref(),
+                    features,
+                    limits,
                 },
                 None,
             )
+            .await?;
+
+        Ok(Framework::from_device(adapter, device, queue))
+    }
+
+    /// Blocking variant of [`FrameworkBuilder::build_async`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn build(self) -> GpuResult<Framework> {
+        futures::executor::block_on(self.build_async())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for Framework {
+    fn default() -> Self {
+        FrameworkBuilder::new()
+            .required_features(wgpu::Features::all())
+            .build()
+            .expect("Failed at Framework creation.")
+    }
+}
+
+impl Framework {
+    #[cfg(target_arch = "wasm32")]
+    pub async fn default() -> Self {
+        FrameworkBuilder::new()
+            .required_features(wgpu::Features::all())
+            .build_async()
             .await
-            .expect("Failed at device creation.");
+            .expect("Failed at Framework creation.")
+    }
 
+    /// Builds a [`Framework`] from an already created device trio, wiring up the
+    /// staging pool. Shared by [`Framework::new`] and [`FrameworkBuilder`].
+    fn from_device(adapter: wgpu::Adapter, device: wgpu::Device, queue: wgpu::Queue) -> Self {
         let info = adapter.get_info();
         log::info!(
             "Using {} ({}) - {:#?}.",
@@ -70,13 +160,75 @@ impl Framework {
 
         let device = Arc::new(device);
 
+        let staging_pool = Mutex::new(StagingPool::new(
+            Arc::clone(&device),
+            Self::DEFAULT_STAGING_POOL_BYTES,
+        ));
+
         Self {
             device,
             queue,
             adapter,
+            staging_pool,
         }
     }
 
+    /// Creates a new [`Framework`] instance from a [`wgpu::Adapter`] and a `polling_time`.
+    ///
+    /// Use this method when there are multiple GPUs in use or when a [`wgpu::Surface`] is required.
+    pub async fn new(adapter: wgpu::Adapter) -> Self {
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: None,
+                    features: adapter.features(), // Change this to allow proper WebGL2 support (in the future™️).
+                    limits: adapter.limits(),     // Bye WebGL2 support :(
+                },
+                None,
+            )
+            .await
+            .expect("Failed at device creation.");
+
+        Self::from_device(adapter, device, queue)
+    }
+
+    /// Default cap, in bytes, on the amount of idle staging memory retained by
+    /// the [`StagingPool`]. 256 MiB.
+    const DEFAULT_STAGING_POOL_BYTES: u64 = 256 * 1024 * 1024;
+
+    /// Pulls a download/upload staging buffer of at least `size` bytes from the
+    /// pool, recycling one when possible.
+    ///
+    /// The buffer must be handed back with [`Framework::release_staging_buffer`]
+    /// once it has been unmapped.
+    pub(crate) fn request_staging_buffer(&self, size: usize, kind: StagingKind) -> wgpu::Buffer {
+        self.staging_pool
+            .lock()
+            .unwrap()
+            .pull(size as u64, kind)
+    }
+
+    /// Returns an unmapped staging buffer to the pool. `size` must match the
+    /// value passed to [`Framework::request_staging_buffer`].
+    pub(crate) fn release_staging_buffer(
+        &self,
+        buffer: wgpu::Buffer,
+        size: usize,
+        kind: StagingKind,
+    ) {
+        self.staging_pool
+            .lock()
+            .unwrap()
+            .push(buffer, size as u64, kind);
+    }
+
+    /// Drops every staging buffer currently retained by the pool, freeing their
+    /// device memory. Useful for long-running applications that want to bound
+    /// memory after a burst of transfers.
+    pub fn clear_staging_pool(&self) {
+        self.staging_pool.lock().unwrap().clear();
+    }
+
     /// Gets info about the adapter that created this [`Framework`].
     pub fn info(&self) -> wgpu::AdapterInfo {
         self.adapter.get_info()
@@ -18,6 +18,15 @@ pub trait PixelInfo {
     fn byte_size() -> usize;
     fn wgpu_format() -> wgpu::TextureFormat;
     fn wgpu_texture_sample() -> wgpu::TextureSampleType;
+
+    /// Returns the linear (non-sRGB) counterpart of this format, or the format
+    /// itself when it is already linear.
+    ///
+    /// Lets an sRGB image be sampled as raw linear data inside a kernel without
+    /// the implicit sRGB → linear conversion happening twice.
+    fn non_srgb_format() -> wgpu::TextureFormat {
+        Self::wgpu_format()
+    }
 }
 
 macro_rules! pixel_info_impl {
@@ -45,12 +54,52 @@ macro_rules! pixel_info_impl {
     };
 }
 
+macro_rules! pixel_info_srgb_impl {
+    ($($name:ident, $size:expr, $format:expr, $sample:expr, $non_srgb:expr, #[$doc:meta]);+) => {
+        $(
+            #[$doc]
+            pub struct $name;
+
+            impl PixelInfo for $name {
+                fn byte_size() -> usize {
+                    $size
+                }
+
+                fn wgpu_format() -> wgpu::TextureFormat {
+                    $format
+                }
+
+                fn wgpu_texture_sample() -> wgpu::TextureSampleType {
+                    $sample
+                }
+
+                fn non_srgb_format() -> wgpu::TextureFormat {
+                    $non_srgb
+                }
+            }
+        )+
+    };
+}
+
 pub mod pixels {
     pixel_info_impl! {
         Rgba8Uint, 4, wgpu::TextureFormat::Rgba8Uint, wgpu::TextureSampleType::Uint, #[doc = "Red, green, blue, and alpha channels. 8 bit integer per channel. Unsigned in shader."];
         Rgba8UintNorm, 4, wgpu::TextureFormat::Rgba8Unorm, wgpu::TextureSampleType::Float { filterable: false }, #[doc = "Red, green, blue, and alpha channels. 8 bit integer per channel. 0, 255 converted to/from float 0, 1 in shader."];
         Rgba8Sint, 4, wgpu::TextureFormat::Rgba8Sint, wgpu::TextureSampleType::Sint, #[doc = "Red, green, blue, and alpha channels. 8 bit integer per channel. Signed in shader."];
-        Rgba8SintNorm, 4, wgpu::TextureFormat::Rgba8Snorm, wgpu::TextureSampleType::Float { filterable: false }, #[doc = "Red, green, blue, and alpha channels. 8 bit integer per channel. -127, 127 converted to/from float -1, 1 in shader."]
+        Rgba8SintNorm, 4, wgpu::TextureFormat::Rgba8Snorm, wgpu::TextureSampleType::Float { filterable: false }, #[doc = "Red, green, blue, and alpha channels. 8 bit integer per channel. -127, 127 converted to/from float -1, 1 in shader."];
+        Rgba32Float, 16, wgpu::TextureFormat::Rgba32Float, wgpu::TextureSampleType::Float { filterable: false }, #[doc = "Red, green, blue, and alpha channels. 32 bit float per channel. Float in shader."];
+        Rgba16Float, 8, wgpu::TextureFormat::Rgba16Float, wgpu::TextureSampleType::Float { filterable: true }, #[doc = "Red, green, blue, and alpha channels. 16 bit float per channel. Float in shader."];
+        Rg32Float, 8, wgpu::TextureFormat::Rg32Float, wgpu::TextureSampleType::Float { filterable: false }, #[doc = "Red and green channels. 32 bit float per channel. Float in shader."];
+        R32Float, 4, wgpu::TextureFormat::R32Float, wgpu::TextureSampleType::Float { filterable: false }, #[doc = "Red channel only. 32 bit float. Float in shader."];
+        R32Uint, 4, wgpu::TextureFormat::R32Uint, wgpu::TextureSampleType::Uint, #[doc = "Red channel only. 32 bit integer. Unsigned in shader."];
+        R16Uint, 2, wgpu::TextureFormat::R16Uint, wgpu::TextureSampleType::Uint, #[doc = "Red channel only. 16 bit integer. Unsigned in shader."];
+        Rgba16Uint, 8, wgpu::TextureFormat::Rgba16Uint, wgpu::TextureSampleType::Uint, #[doc = "Red, green, blue, and alpha channels. 16 bit integer per channel. Unsigned in shader."];
+        R8Unorm, 1, wgpu::TextureFormat::R8Unorm, wgpu::TextureSampleType::Float { filterable: true }, #[doc = "Red channel only. 8 bit integer. 0, 255 converted to/from float 0, 1 in shader."]
+    }
+
+    pixel_info_srgb_impl! {
+        Rgba8UnormSrgb, 4, wgpu::TextureFormat::Rgba8UnormSrgb, wgpu::TextureSampleType::Float { filterable: true }, wgpu::TextureFormat::Rgba8Unorm, #[doc = "Red, green, blue, and alpha channels. 8 bit integer per channel. sRGB encoded; decoded to linear float 0, 1 in shader."];
+        Bgra8UnormSrgb, 4, wgpu::TextureFormat::Bgra8UnormSrgb, wgpu::TextureSampleType::Float { filterable: true }, wgpu::TextureFormat::Bgra8Unorm, #[doc = "Blue, green, red, and alpha channels. 8 bit integer per channel. sRGB encoded; decoded to linear float 0, 1 in shader."]
     }
 }
 
@@ -92,12 +141,23 @@ cfg_if::cfg_if! {
         }
 
         gpgpu_to_image_impl! {
-            ::image::Rgba<u8>, pixels::Rgba8Uint, pixels::Rgba8UintNorm, pixels::Rgba8Sint, pixels::Rgba8SintNorm
+            ::image::Rgba<u8>, pixels::Rgba8Uint, pixels::Rgba8UintNorm, pixels::Rgba8Sint, pixels::Rgba8SintNorm;
+            ::image::Luma<u8>, pixels::R8Unorm;
+            ::image::Luma<u16>, pixels::R16Uint;
+            ::image::Rgba<u16>, pixels::Rgba16Uint;
+            ::image::Rgba<f32>, pixels::Rgba32Float
         }
 
         image_to_gpgpu_impl! {
             ::image::Rgba<u8>, pixels::Rgba8Uint, pixels::Rgba8UintNorm;
-            ::image::Rgba<i8>, pixels::Rgba8Sint, pixels::Rgba8SintNorm
+            ::image::Rgba<i8>, pixels::Rgba8Sint, pixels::Rgba8SintNorm;
+            // `image::Rgb<u8>` has no GPU-side RGB8 texture; it is padded to RGBA8
+            // and therefore round-trips back as `image::Rgba<u8>`.
+            ::image::Rgb<u8>, pixels::Rgba8Uint, pixels::Rgba8UintNorm;
+            ::image::Luma<u8>, pixels::R8Unorm, pixels::R8Unorm;
+            ::image::Luma<u16>, pixels::R16Uint, pixels::R16Uint;
+            ::image::Rgba<u16>, pixels::Rgba16Uint, pixels::Rgba16Uint;
+            ::image::Rgba<f32>, pixels::Rgba32Float, pixels::Rgba32Float
         }
     }
 }
@@ -1,8 +1,13 @@
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 use wgpu::util::DeviceExt;
 
-use crate::{GpuBuffer, GpuResult};
+use crate::primitives::pixels::Rgba8UintNorm;
+use crate::recording::{GpuBufferDownload, Recording};
+use crate::staging_pool::StagingKind;
+use crate::timestamp::{GpuTimings, TimestampScope};
+use crate::{GpuBuffer, GpuError, GpuImage, GpuResult};
 
 impl<'fw, T> GpuBuffer<'fw, T>
 where
@@ -83,7 +88,9 @@ where
     /// In order for this future to resolve, [`Framework::poll`](crate::Framework::poll) or [`Framework::blocking_poll`](crate::Framework::poll)
     /// must be invoked.
     pub async fn read_async(&self) -> GpuResult<Vec<T>> {
-        let staging = self.fw.create_download_staging_buffer(self.size);
+        let staging = self
+            .fw
+            .request_staging_buffer(self.size, StagingKind::Download);
 
         let mut encoder = self
             .fw
@@ -95,7 +102,7 @@ where
 
         self.fw.queue.submit(Some(encoder.finish()));
 
-        let buff_slice = staging.slice(..);
+        let buff_slice = staging.slice(..self.size as u64);
         let buf_future = buff_slice.map_async(wgpu::MapMode::Read);
 
         buf_future.await?;
@@ -105,13 +112,17 @@ where
 
         drop(data);
         staging.unmap();
+        self.fw
+            .release_staging_buffer(staging, self.size, StagingKind::Download);
 
         Ok(result)
     }
 
     /// Blocking read of the content of the [`GpuBuffer`] into a [`Vec`].
     pub fn read(&self) -> GpuResult<Vec<T>> {
-        let staging = self.fw.create_download_staging_buffer(self.size);
+        let staging = self
+            .fw
+            .request_staging_buffer(self.size, StagingKind::Download);
 
         let mut encoder = self
             .fw
@@ -123,7 +134,7 @@ where
 
         self.fw.queue.submit(Some(encoder.finish()));
 
-        let buff_slice = staging.slice(..);
+        let buff_slice = staging.slice(..self.size as u64);
         let buf_future = buff_slice.map_async(wgpu::MapMode::Read);
 
         self.fw.blocking_poll();
@@ -135,28 +146,70 @@ where
 
         drop(data);
         staging.unmap();
+        self.fw
+            .release_staging_buffer(staging, self.size, StagingKind::Download);
 
         Ok(result)
     }
 
-    /// Asyncronously writes the contents of `data` into the [`GpuBuffer`].
+    /// Blocking read like [`GpuBuffer::read`], additionally measuring how long
+    /// the device copy takes with a [`TimestampScope`].
     ///
-    /// In order for this future to resolve, [`Framework::poll`](crate::Framework::poll) or [`Framework::blocking_poll`](crate::Framework::blocking_poll)
-    /// must be invoked.
-    pub async fn write_async(&mut self, data: &[T]) -> GpuResult<()> {
-        let staging = self.fw.create_upload_staging_buffer(self.size);
+    /// The device must support timestamp queries
+    /// (see [`Framework::timestamps_supported`](crate::Framework::timestamps_supported)),
+    /// or this returns [`GpuError::TimestampsNotSupported`].
+    pub fn read_profiled(&self) -> GpuResult<(Vec<T>, GpuTimings)> {
+        if !self.fw.timestamps_supported() {
+            return Err(GpuError::TimestampsNotSupported);
+        }
+
+        let staging = self
+            .fw
+            .request_staging_buffer(self.size, StagingKind::Download);
+        let scope = TimestampScope::new(self.fw);
 
         let mut encoder = self
             .fw
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("GpuBuffer::write_async"),
+                label: Some("GpuBuffer::read_profiled"),
             });
-        encoder.copy_buffer_to_buffer(&staging, 0, &self.storage, 0, self.size as u64);
+        scope.write(&mut encoder, 0);
+        encoder.copy_buffer_to_buffer(&self.storage, 0, &staging, 0, self.size as u64);
+        scope.write(&mut encoder, 1);
+        scope.resolve(&mut encoder);
 
         self.fw.queue.submit(Some(encoder.finish()));
 
-        let buff_slice = self.storage.slice(..);
+        let buff_slice = staging.slice(..self.size as u64);
+        let buf_future = buff_slice.map_async(wgpu::MapMode::Read);
+
+        self.fw.poll_blocking();
+        futures::executor::block_on(buf_future)?;
+
+        let data = buff_slice.get_mapped_range();
+        let result = bytemuck::cast_slice(&data).to_vec();
+
+        drop(data);
+        staging.unmap();
+        self.fw
+            .release_staging_buffer(staging, self.size, StagingKind::Download);
+
+        let timings = scope.elapsed()?;
+
+        Ok((result, timings))
+    }
+
+    /// Asyncronously writes the contents of `data` into the [`GpuBuffer`].
+    ///
+    /// In order for this future to resolve, [`Framework::poll`](crate::Framework::poll) or [`Framework::blocking_poll`](crate::Framework::blocking_poll)
+    /// must be invoked.
+    pub async fn write_async(&mut self, data: &[T]) -> GpuResult<()> {
+        let staging = self
+            .fw
+            .request_staging_buffer(self.size, StagingKind::Upload);
+
+        let buff_slice = staging.slice(..self.size as u64);
         let buf_future = buff_slice.map_async(wgpu::MapMode::Write);
 
         buf_future.await?;
@@ -165,11 +218,63 @@ where
         write_view.copy_from_slice(bytemuck::cast_slice(data));
 
         drop(write_view);
-        self.storage.unmap();
+        staging.unmap();
+
+        let mut encoder = self
+            .fw
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("GpuBuffer::write_async"),
+            });
+        encoder.copy_buffer_to_buffer(&staging, 0, &self.storage, 0, self.size as u64);
+
+        self.fw.queue.submit(Some(encoder.finish()));
+        self.fw
+            .release_staging_buffer(staging, self.size, StagingKind::Upload);
 
         Ok(())
     }
 
+    /// Enqueues a read of this [`GpuBuffer`] into the `recording` instead of
+    /// submitting immediately, returning a [`GpuBufferDownload`] handle.
+    ///
+    /// After [`Framework::run`](crate::Framework::run), call
+    /// [`GpuBufferDownload::map`] and then [`GpuBufferDownload::read`] on the
+    /// handle to obtain the data.
+    pub fn read_into_recording<'r>(
+        &'r self,
+        recording: &mut Recording<'r>,
+    ) -> GpuBufferDownload<'fw, T> {
+        let staging = Arc::new(self.fw.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GpuBuffer::read_into_recording"),
+            size: self.size as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }));
+
+        recording.push_download(&self.storage, Arc::clone(&staging), self.size as u64);
+
+        GpuBufferDownload::new(self.fw, staging)
+    }
+
+    /// Enqueues a write of `data` into this [`GpuBuffer`] on the `recording`
+    /// instead of submitting immediately.
+    ///
+    /// The data is copied into an upload staging buffer right away and flushed
+    /// to the device when [`Framework::run`](crate::Framework::run) is invoked.
+    pub fn write_via_recording<'r>(&'r self, recording: &mut Recording<'r>, data: &[T]) {
+        let staging = self
+            .fw
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("GpuBuffer::write_via_recording"),
+                contents: bytemuck::cast_slice(data),
+                usage: wgpu::BufferUsages::COPY_SRC,
+            });
+
+        recording.push_upload(staging, &self.storage, self.size as u64);
+    }
+
     /// Writes the `data` information into the [`GpuBuffer`] immediately.
     pub fn write(&mut self, data: &[T]) {
         self.fw
@@ -186,3 +291,190 @@ where
         self.fw.queue.submit(Some(encoder.finish()));
     }
 }
+
+/// Uniform passed to the packing kernels, mapping a 2D texel to its linear
+/// index in the flat [`GpuBuffer`] as `global_id.y * width + global_id.x`.
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct PackConfig {
+    width: u32,
+    height: u32,
+}
+
+/// Compute shader bridging a flat `GpuBuffer<u32>` and an RGBA8 image texture.
+///
+/// `unpack` expands each `u32` into an RGBA8 texel with `unpack4x8unorm`, while
+/// `pack` folds each texel back into a `u32` with `pack4x8unorm`.
+const PACKING_SHADER: &str = r#"
+struct Config {
+    width: u32,
+    height: u32,
+}
+
+@group(0) @binding(0) var<storage, read_write> buf: array<u32>;
+@group(0) @binding(2) var<uniform> config: Config;
+
+@group(0) @binding(1) var dst: texture_storage_2d<rgba8unorm, write>;
+
+@compute @workgroup_size(8, 8)
+fn unpack(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= config.width || gid.y >= config.height) {
+        return;
+    }
+    let index = gid.y * config.width + gid.x;
+    let color = unpack4x8unorm(buf[index]);
+    textureStore(dst, vec2<i32>(i32(gid.x), i32(gid.y)), color);
+}
+
+@group(0) @binding(3) var src: texture_2d<f32>;
+
+@compute @workgroup_size(8, 8)
+fn pack(@builtin(global_invocation_id) gid: vec3<u32>) {
+    if (gid.x >= config.width || gid.y >= config.height) {
+        return;
+    }
+    let index = gid.y * config.width + gid.x;
+    let color = textureLoad(src, vec2<i32>(i32(gid.x), i32(gid.y)), 0);
+    buf[index] = pack4x8unorm(color);
+}
+"#;
+
+impl<'fw> GpuBuffer<'fw, u32> {
+    /// Unpacks each `u32` of this buffer into an RGBA8 texel of `image` using a
+    /// built-in `unpack4x8unorm`/`textureStore` compute pass.
+    ///
+    /// The `image` must have been created with storage-binding usage. Its
+    /// dimensions drive the dispatch; texel `(x, y)` is read from buffer index
+    /// `y * width + x`.
+    pub fn into_image(&self, image: &GpuImage<'fw, Rgba8UintNorm>) {
+        let (width, height) = image.dimensions();
+        let config = self.pack_config_buffer(width, height);
+
+        let module = self.pack_shader_module();
+        let pipeline =
+            self.fw
+                .device
+                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: Some("GpuBuffer::into_image"),
+                    layout: None,
+                    module: &module,
+                    entry_point: "unpack",
+                });
+
+        let view = image.create_view();
+        let bind_group = self.fw.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("GpuBuffer::into_image"),
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.storage.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: config.as_entire_binding(),
+                },
+            ],
+        });
+
+        self.dispatch_packing(&pipeline, &bind_group, width, height, "GpuBuffer::into_image");
+    }
+
+    /// Packs the RGBA8 texels of `image` into this buffer using a built-in
+    /// `pack4x8unorm`/`textureLoad` compute pass; the inverse of
+    /// [`GpuBuffer::into_image`].
+    ///
+    /// The `image` must have been created with texture-binding usage.
+    pub fn pack_image_into_buffer(&mut self, image: &GpuImage<'fw, Rgba8UintNorm>) {
+        let (width, height) = image.dimensions();
+        let config = self.pack_config_buffer(width, height);
+
+        let module = self.pack_shader_module();
+        let pipeline =
+            self.fw
+                .device
+                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: Some("GpuBuffer::pack_image_into_buffer"),
+                    layout: None,
+                    module: &module,
+                    entry_point: "pack",
+                });
+
+        let view = image.create_view();
+        let bind_group = self.fw.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("GpuBuffer::pack_image_into_buffer"),
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.storage.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: config.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+            ],
+        });
+
+        self.dispatch_packing(
+            &pipeline,
+            &bind_group,
+            width,
+            height,
+            "GpuBuffer::pack_image_into_buffer",
+        );
+    }
+
+    fn pack_shader_module(&self) -> wgpu::ShaderModule {
+        self.fw
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("gpgpu::packing"),
+                source: wgpu::ShaderSource::Wgsl(PACKING_SHADER.into()),
+            })
+    }
+
+    fn pack_config_buffer(&self, width: u32, height: u32) -> wgpu::Buffer {
+        self.fw
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("gpgpu::packing config"),
+                contents: bytemuck::bytes_of(&PackConfig { width, height }),
+                usage: wgpu::BufferUsages::UNIFORM,
+            })
+    }
+
+    fn dispatch_packing(
+        &self,
+        pipeline: &wgpu::ComputePipeline,
+        bind_group: &wgpu::BindGroup,
+        width: u32,
+        height: u32,
+        label: &str,
+    ) {
+        let mut encoder = self
+            .fw
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(label) });
+
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some(label),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, bind_group, &[]);
+            pass.dispatch_workgroups((width + 7) / 8, (height + 7) / 8, 1);
+        }
+
+        self.fw.queue.submit(Some(encoder.finish()));
+    }
+}
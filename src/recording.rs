@@ -0,0 +1,215 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::timestamp::{GpuTimings, TimestampScope};
+use crate::{Framework, GpuError, GpuResult};
+
+/// A single deferred transfer operation accumulated in a [`Recording`].
+pub(crate) enum Command<'r> {
+    /// Copy `size` bytes from `src` to `dst` at the given offsets.
+    CopyBufferToBuffer {
+        src: &'r wgpu::Buffer,
+        src_offset: u64,
+        dst: &'r wgpu::Buffer,
+        dst_offset: u64,
+        size: u64,
+    },
+    /// Copy the whole `staging` buffer (already filled on the host) into `dst`.
+    Upload {
+        staging: wgpu::Buffer,
+        dst: &'r wgpu::Buffer,
+        size: u64,
+    },
+    /// Copy `src` into the download `staging` buffer, to be mapped afterwards.
+    Download {
+        src: &'r wgpu::Buffer,
+        staging: Arc<wgpu::Buffer>,
+        size: u64,
+    },
+}
+
+/// A list of deferred buffer operations that are encoded into a single
+/// [`wgpu::CommandEncoder`] and dispatched with one [`wgpu::Queue::submit`] by
+/// [`Framework::run`].
+///
+/// Recording transfers instead of submitting them one by one amortizes the cost
+/// of command-encoder creation, submission and fencing across an entire batch,
+/// which matters when dozens of inputs are staged before a dispatch.
+#[derive(Default)]
+pub struct Recording<'r> {
+    pub(crate) commands: Vec<Command<'r>>,
+}
+
+impl<'r> Recording<'r> {
+    /// Creates an empty [`Recording`].
+    pub fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+        }
+    }
+
+    /// Enqueues a raw buffer-to-buffer copy.
+    pub fn copy_buffer_to_buffer(
+        &mut self,
+        src: &'r wgpu::Buffer,
+        src_offset: u64,
+        dst: &'r wgpu::Buffer,
+        dst_offset: u64,
+        size: u64,
+    ) {
+        self.commands.push(Command::CopyBufferToBuffer {
+            src,
+            src_offset,
+            dst,
+            dst_offset,
+            size,
+        });
+    }
+
+    pub(crate) fn push_upload(&mut self, staging: wgpu::Buffer, dst: &'r wgpu::Buffer, size: u64) {
+        self.commands.push(Command::Upload { staging, dst, size });
+    }
+
+    pub(crate) fn push_download(
+        &mut self,
+        src: &'r wgpu::Buffer,
+        staging: Arc<wgpu::Buffer>,
+        size: u64,
+    ) {
+        self.commands
+            .push(Command::Download { src, staging, size });
+    }
+}
+
+impl Framework {
+    /// Encodes every operation of a [`Recording`] into a single command encoder
+    /// and submits it to the queue once.
+    ///
+    /// After running, call [`GpuBufferDownload::map`] on the handles returned
+    /// by [`read_into_recording`](crate::GpuBuffer::read_into_recording) to
+    /// resolve them.
+    pub fn run(&self, recording: &Recording) {
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Framework::run"),
+            });
+
+        self.encode_recording(&mut encoder, recording);
+
+        self.queue.submit(Some(encoder.finish()));
+    }
+
+    /// Like [`Framework::run`], additionally measuring the device time taken by
+    /// the whole batch with a [`TimestampScope`].
+    ///
+    /// The device must support timestamp queries
+    /// (see [`Framework::timestamps_supported`](crate::Framework::timestamps_supported)),
+    /// or this returns [`GpuError::TimestampsNotSupported`].
+    pub fn run_profiled(&self, recording: &Recording) -> GpuResult<GpuTimings> {
+        if !self.timestamps_supported() {
+            return Err(GpuError::TimestampsNotSupported);
+        }
+
+        let scope = TimestampScope::new(self);
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Framework::run_profiled"),
+            });
+
+        scope.write(&mut encoder, 0);
+        self.encode_recording(&mut encoder, recording);
+        scope.write(&mut encoder, 1);
+        scope.resolve(&mut encoder);
+
+        self.queue.submit(Some(encoder.finish()));
+
+        scope.elapsed()
+    }
+
+    fn encode_recording(&self, encoder: &mut wgpu::CommandEncoder, recording: &Recording) {
+        for command in &recording.commands {
+            match command {
+                Command::CopyBufferToBuffer {
+                    src,
+                    src_offset,
+                    dst,
+                    dst_offset,
+                    size,
+                } => encoder.copy_buffer_to_buffer(src, *src_offset, dst, *dst_offset, *size),
+                Command::Upload { staging, dst, size } => {
+                    encoder.copy_buffer_to_buffer(staging, 0, dst, 0, *size)
+                }
+                Command::Download { src, staging, size } => {
+                    encoder.copy_buffer_to_buffer(src, 0, staging, 0, *size)
+                }
+            }
+        }
+    }
+}
+
+/// A handle to data copied into a download staging buffer by a [`Recording`].
+///
+/// Once the recording has been run, call [`GpuBufferDownload::map`] (or
+/// [`GpuBufferDownload::map_async`] alongside an external poll loop) and then
+/// [`GpuBufferDownload::read`] to obtain the mapped contents.
+pub struct GpuBufferDownload<'fw, T> {
+    fw: &'fw Framework,
+    staging: Arc<wgpu::Buffer>,
+    _marker: PhantomData<T>,
+}
+
+impl<'fw, T> GpuBufferDownload<'fw, T>
+where
+    T: bytemuck::Pod,
+{
+    pub(crate) fn new(fw: &'fw Framework, staging: Arc<wgpu::Buffer>) -> Self {
+        Self {
+            fw,
+            staging,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Blocks until the staging buffer is mapped for reading, driving
+    /// [`Framework::poll_blocking`] itself.
+    ///
+    /// Call after running the recording with [`Framework::run`].
+    pub fn map(&self) -> GpuResult<()> {
+        let future = self.staging.slice(..).map_async(wgpu::MapMode::Read);
+
+        self.fw.poll_blocking();
+        futures::executor::block_on(future)?;
+
+        Ok(())
+    }
+
+    /// Registers the mapping and returns a future that resolves once it
+    /// completes, without polling the device itself.
+    ///
+    /// Unlike [`GpuBufferDownload::map`], awaiting this future on the same
+    /// thread that would otherwise drive [`Framework::poll`] deadlocks,
+    /// since nothing ever runs the poll the map callback waits on; only use
+    /// it when another task or thread polls `fw` concurrently while this is
+    /// awaited. Call after running the recording with [`Framework::run`].
+    pub async fn map_async(&self) -> GpuResult<()> {
+        self.staging.slice(..).map_async(wgpu::MapMode::Read).await?;
+        Ok(())
+    }
+
+    /// Reads the mapped staging buffer into a [`Vec`]. Must be called after
+    /// [`GpuBufferDownload::map`] or [`GpuBufferDownload::map_async`] has
+    /// resolved.
+    pub fn read(&self) -> Vec<T> {
+        let slice = self.staging.slice(..);
+        let data = slice.get_mapped_range();
+        let result = bytemuck::cast_slice(&data).to_vec();
+
+        drop(data);
+        self.staging.unmap();
+
+        result
+    }
+}
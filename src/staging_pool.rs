@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Whether a staging buffer is used to download data from the GPU or to upload
+/// data to it. Determines the buffer usages and the [`wgpu::MapMode`] it is
+/// mapped with.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum StagingKind {
+    /// Device → host transfer. Mapped with [`wgpu::MapMode::Read`].
+    Download,
+    /// Host → device transfer. Mapped with [`wgpu::MapMode::Write`].
+    Upload,
+}
+
+impl StagingKind {
+    fn usages(self) -> wgpu::BufferUsages {
+        match self {
+            StagingKind::Download => wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            StagingKind::Upload => wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
+        }
+    }
+}
+
+/// A recycling pool of unmapped staging buffers, keyed by `(size_class, kind)`.
+///
+/// Every host ↔ device transfer needs a short-lived staging buffer. Allocating
+/// a fresh one per transfer thrashes the allocator and the wgpu buffer cache
+/// for workloads that stream data every frame. Instead, the pool rounds each
+/// requested size up to a power-of-two class, hands out a recycled buffer for
+/// the duration of the transfer and takes it back once it has been unmapped.
+///
+/// The amount of retained (idle) bytes is bounded by `max_retained_bytes`;
+/// buffers released beyond that bound are dropped instead of being kept.
+pub struct StagingPool {
+    device: Arc<wgpu::Device>,
+    free: HashMap<(u64, StagingKind), Vec<wgpu::Buffer>>,
+    retained_bytes: u64,
+    max_retained_bytes: u64,
+}
+
+impl StagingPool {
+    /// The smallest size class handed out by the pool, in bytes.
+    const MIN_CLASS: u64 = 256;
+
+    /// Creates an empty pool that will retain at most `max_retained_bytes` of
+    /// idle staging buffers.
+    pub fn new(device: Arc<wgpu::Device>, max_retained_bytes: u64) -> Self {
+        Self {
+            device,
+            free: HashMap::new(),
+            retained_bytes: 0,
+            max_retained_bytes,
+        }
+    }
+
+    /// Rounds `size` up to its power-of-two size class, clamped to [`Self::MIN_CLASS`].
+    fn size_class(size: u64) -> u64 {
+        size.max(Self::MIN_CLASS).next_power_of_two()
+    }
+
+    /// Pulls a staging buffer able to hold `size` bytes, recycling an idle one
+    /// of the matching class when available or creating a fresh one otherwise.
+    ///
+    /// The returned buffer must be given back with [`StagingPool::push`] once it
+    /// has been unmapped.
+    pub fn pull(&mut self, size: u64, kind: StagingKind) -> wgpu::Buffer {
+        let class = Self::size_class(size);
+
+        if let Some(buffers) = self.free.get_mut(&(class, kind)) {
+            if let Some(buffer) = buffers.pop() {
+                self.retained_bytes = self.retained_bytes.saturating_sub(class);
+                return buffer;
+            }
+        }
+
+        self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("gpgpu::StagingPool"),
+            size: class,
+            usage: kind.usages(),
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Returns a previously pulled, now unmapped staging buffer to the pool.
+    ///
+    /// `size` must be the same value that was passed to [`StagingPool::pull`].
+    /// The buffer is dropped instead of retained once the pool would exceed its
+    /// retained-bytes cap.
+    pub fn push(&mut self, buffer: wgpu::Buffer, size: u64, kind: StagingKind) {
+        let class = Self::size_class(size);
+
+        if self.retained_bytes + class > self.max_retained_bytes {
+            return;
+        }
+
+        self.retained_bytes += class;
+        self.free.entry((class, kind)).or_default().push(buffer);
+    }
+
+    /// Drops every retained staging buffer, freeing their device memory.
+    pub fn clear(&mut self) {
+        self.free.clear();
+        self.retained_bytes = 0;
+    }
+}
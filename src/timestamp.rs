@@ -0,0 +1,117 @@
+use crate::{Framework, GpuResult};
+
+/// Result of a [`TimestampScope`] measurement, holding the elapsed device time
+/// between its start and end timestamps.
+#[derive(Clone, Copy, Debug)]
+pub struct GpuTimings {
+    /// Raw tick of the opening timestamp.
+    pub start_ticks: u64,
+    /// Raw tick of the closing timestamp.
+    pub end_ticks: u64,
+    /// Nanoseconds per tick, as reported by [`wgpu::Queue::get_timestamp_period`].
+    pub period_ns: f32,
+}
+
+impl GpuTimings {
+    /// Elapsed device time between the two timestamps, in nanoseconds.
+    pub fn elapsed_ns(&self) -> f64 {
+        self.end_ticks.saturating_sub(self.start_ticks) as f64 * self.period_ns as f64
+    }
+}
+
+/// A pair of GPU timestamp queries used to measure how long the work encoded
+/// between them takes on device.
+///
+/// Requires the [`wgpu::Features::TIMESTAMP_QUERY`] feature; check for it with
+/// [`Framework::timestamps_supported`]. Call [`TimestampScope::write`] before
+/// and after the commands of interest, [`TimestampScope::resolve`] at the end
+/// of the encoder, submit it, then read the result with
+/// [`TimestampScope::elapsed`].
+pub struct TimestampScope<'fw> {
+    fw: &'fw Framework,
+    query_set: wgpu::QuerySet,
+    resolve: wgpu::Buffer,
+    download: wgpu::Buffer,
+}
+
+impl<'fw> TimestampScope<'fw> {
+    const COUNT: u32 = 2;
+    const BYTES: u64 = Self::COUNT as u64 * std::mem::size_of::<u64>() as u64;
+
+    /// Allocates a query set and the buffers needed to resolve and read back a
+    /// start/end timestamp pair.
+    pub fn new(fw: &'fw Framework) -> Self {
+        let query_set = fw.device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("gpgpu::TimestampScope"),
+            ty: wgpu::QueryType::Timestamp,
+            count: Self::COUNT,
+        });
+
+        let resolve = fw.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("gpgpu::TimestampScope resolve"),
+            size: Self::BYTES,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let download = fw.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("gpgpu::TimestampScope download"),
+            size: Self::BYTES,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            fw,
+            query_set,
+            resolve,
+            download,
+        }
+    }
+
+    /// Writes the timestamp `index` (`0` for the start, `1` for the end) into
+    /// the given encoder.
+    pub fn write(&self, encoder: &mut wgpu::CommandEncoder, index: u32) {
+        encoder.write_timestamp(&self.query_set, index);
+    }
+
+    /// Resolves the queries and stages them for readback. Must be encoded after
+    /// both [`TimestampScope::write`] calls and before the encoder is submitted.
+    pub fn resolve(&self, encoder: &mut wgpu::CommandEncoder) {
+        encoder.resolve_query_set(&self.query_set, 0..Self::COUNT, &self.resolve, 0);
+        encoder.copy_buffer_to_buffer(&self.resolve, 0, &self.download, 0, Self::BYTES);
+    }
+
+    /// Maps the resolved timestamps and converts them to a [`GpuTimings`].
+    ///
+    /// Must be called after the encoder holding the [`TimestampScope::resolve`]
+    /// commands has been submitted.
+    pub fn elapsed(&self) -> GpuResult<GpuTimings> {
+        let slice = self.download.slice(..);
+        let future = slice.map_async(wgpu::MapMode::Read);
+
+        self.fw.poll_blocking();
+        futures::executor::block_on(future)?;
+
+        let data = slice.get_mapped_range();
+        let ticks: &[u64] = bytemuck::cast_slice(&data);
+        let timings = GpuTimings {
+            start_ticks: ticks[0],
+            end_ticks: ticks[1],
+            period_ns: self.fw.queue.get_timestamp_period(),
+        };
+
+        drop(data);
+        self.download.unmap();
+
+        Ok(timings)
+    }
+}
+
+impl Framework {
+    /// Returns `true` if this [`Framework`]'s device supports timestamp queries
+    /// and can therefore be used to build a [`TimestampScope`].
+    pub fn timestamps_supported(&self) -> bool {
+        self.features().contains(wgpu::Features::TIMESTAMP_QUERY)
+    }
+}